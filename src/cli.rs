@@ -0,0 +1,116 @@
+//! Subcommands the `embeddedengine` binary dispatches through [`crate::run`].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use clap::Subcommand;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::executor::{self, Executor, TaskStorage};
+use crate::script::Expr;
+use crate::{filter, script, state};
+use crate::Result;
+
+/// Top-level engine subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Load and execute an engine script.
+    Run {
+        /// Path to the script to execute.
+        file: PathBuf,
+    },
+    /// Start an interactive REPL.
+    Repl,
+    /// Parse and validate a script without executing it.
+    Check {
+        /// Path to the script to validate.
+        file: PathBuf,
+    },
+    /// Query or transform the engine's runtime state with a jq-like expression.
+    Filter {
+        /// The jaq expression to evaluate against the engine state.
+        expr: String,
+    },
+}
+
+/// The executor `run`/`repl` spawn each parsed script onto.
+static EXECUTOR: Executor = Executor::new();
+
+pub(crate) fn run_file(path: &Path) -> Result<()> {
+    let source = fs::read_to_string(path)?;
+    log::info!("running {}", path.display());
+    let expr = script::parse(&source)?;
+    println!("{}", eval_on_executor(expr));
+    Ok(())
+}
+
+pub(crate) fn check_file(path: &Path) -> Result<()> {
+    let source = fs::read_to_string(path)?;
+    script::parse(&source)?;
+    println!("{}: ok", path.display());
+    Ok(())
+}
+
+/// Runs an interactive read-eval-print loop over the same parse+evaluate
+/// path as [`run_file`], until the user exits with Ctrl-C or Ctrl-D.
+pub(crate) fn repl() -> Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    log::info!("repl ready, Ctrl-D to exit");
+    loop {
+        match editor.readline("eengine> ") {
+            Ok(line) => {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                editor.add_history_entry(line.as_str())?;
+                match script::parse(&line) {
+                    Ok(expr) => println!("{}", eval_on_executor(expr)),
+                    Err(err) => eprintln!("error: {err}"),
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates `expr` by spawning it as a task on [`EXECUTOR`] and running the
+/// executor until that task completes.
+///
+/// Scripts are purely synchronous today (see [`Expr::eval`]), so the task
+/// finishes on its very first poll - but routing evaluation through a real
+/// spawned task, rather than calling `eval` inline, is what lets a future
+/// version of the script language suspend on engine primitives like
+/// [`crate::time::Timer`] without the CLI changing at all.
+fn eval_on_executor(expr: Expr) -> f64 {
+    let result = Arc::new(Mutex::new(None));
+    let task_result = result.clone();
+    // `TaskStorage::spawn` refuses to back a second task once one has been
+    // spawned, so a fresh `run`/repl line needs its own storage rather than
+    // one `static` shared across every evaluation; leaking it is the usual
+    // trick for getting the `'static` a no-alloc executor requires out of a
+    // hosted, std caller like this CLI.
+    let storage: &'static TaskStorage<_> = Box::leak(Box::new(TaskStorage::new()));
+    EXECUTOR.spawner().spawn(storage, async move {
+        *task_result.lock().unwrap() = Some(expr.eval());
+    });
+    EXECUTOR.run_until(|| executor::spawned_tasks() == executor::completed_tasks());
+    let value = result
+        .lock()
+        .unwrap()
+        .take()
+        .expect("spawned task completed before run_until returned");
+    value
+}
+
+/// Evaluates `expr` against a fresh snapshot of the engine's runtime state
+/// and prints each value it yields.
+pub(crate) fn run_filter(expr: &str) -> Result<()> {
+    for value in filter::eval(expr, state::snapshot())? {
+        println!("{value}");
+    }
+    Ok(())
+}
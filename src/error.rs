@@ -0,0 +1,63 @@
+//! Error type shared across the crate.
+
+use std::fmt;
+
+use rustyline::error::ReadlineError;
+
+use crate::script::ParseError;
+
+/// Convenience alias for results produced by EmbeddedEngine.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors that can surface from running the engine.
+#[derive(Debug)]
+pub enum Error {
+    /// Wraps an I/O failure, e.g. reading a script file.
+    Io(std::io::Error),
+    /// A script failed to parse.
+    Parse(ParseError),
+    /// The REPL's line editor failed outside of a normal Ctrl-C/Ctrl-D exit.
+    Readline(ReadlineError),
+    /// A `--filter`/`filter` expression failed to parse or evaluate.
+    Filter(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {err}"),
+            Error::Parse(err) => write!(f, "parse error: {err}"),
+            Error::Readline(err) => write!(f, "repl error: {err}"),
+            Error::Filter(message) => write!(f, "filter error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Parse(err) => Some(err),
+            Error::Readline(err) => Some(err),
+            Error::Filter(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<ReadlineError> for Error {
+    fn from(err: ReadlineError) -> Self {
+        Error::Readline(err)
+    }
+}
@@ -0,0 +1,113 @@
+//! The executor's poll loop.
+
+use crate::time::{self, Duration};
+
+use super::run_queue;
+use super::spawner::Spawner;
+
+/// Hook invoked when the run queue is empty and there is no work left to
+/// poll, with the duration until the nearest armed timer, if any.
+///
+/// Defaults to [`std::thread::park`]/[`std::thread::park_timeout`], which is
+/// correct on top of an OS, but can be swapped for a `WFE`/`SEV` pair (or
+/// any other low-power wait, honoring or ignoring the timeout) on bare-metal
+/// targets.
+pub type IdleHook = fn(Option<Duration>);
+
+fn park(timeout: Option<Duration>) {
+    match timeout {
+        Some(timeout) => std::thread::park_timeout(timeout.into()),
+        None => std::thread::park(),
+    }
+}
+
+/// A static, no-alloc async task executor.
+///
+/// Tasks are polled from an intrusive run queue shared by every `Executor`
+/// in the process (see [`super::run_queue`]); this struct itself only holds
+/// the idle hook used when that queue is momentarily empty.
+pub struct Executor {
+    idle_hook: IdleHook,
+}
+
+impl Executor {
+    /// Creates an executor that parks the current thread when idle.
+    pub const fn new() -> Self {
+        Self { idle_hook: park }
+    }
+
+    /// Creates an executor with a custom idle hook, e.g. a `WFE` spin for
+    /// bare-metal targets.
+    pub const fn with_idle_hook(idle_hook: IdleHook) -> Self {
+        Self { idle_hook }
+    }
+
+    /// Returns a [`Spawner`] for submitting tasks to run on this executor.
+    pub const fn spawner(&'static self) -> Spawner {
+        Spawner::new()
+    }
+
+    /// Runs the executor loop forever.
+    ///
+    /// Each iteration drains the entire run queue and polls every task in
+    /// that batch exactly once. Tasks woken while this batch is being
+    /// polled land on the queue drained by `run_queue::take_all`, which was
+    /// left empty at the start of the iteration, so they are only picked up
+    /// on the *next* iteration - no single task can starve the rest by
+    /// repeatedly waking itself.
+    ///
+    /// When a pass polls nothing and no timer has expired, the loop parks
+    /// until the nearest timer deadline (or indefinitely, if none is armed)
+    /// rather than spinning; a push onto the run queue from any thread
+    /// unparks it early.
+    ///
+    /// Suited to a bare-metal `main` that spawns its tasks once and then has
+    /// nothing else to do. Hosted callers that spawn a fixed batch of tasks
+    /// and want control back once it finishes want [`Executor::run_until`].
+    pub fn run(&'static self) -> ! {
+        run_queue::register_executor_thread();
+        loop {
+            self.poll_pass();
+        }
+    }
+
+    /// Runs the same poll loop as [`Executor::run`], but returns as soon as
+    /// `done` reports `true` instead of running forever.
+    ///
+    /// `done` is checked before each pass over the run queue, so a caller
+    /// that spawns its tasks first and then compares
+    /// [`super::spawned_tasks`] against [`super::completed_tasks`] will stop
+    /// exactly when that batch has finished.
+    pub fn run_until(&'static self, mut done: impl FnMut() -> bool) {
+        run_queue::register_executor_thread();
+        while !done() {
+            self.poll_pass();
+        }
+    }
+
+    /// Drains the run queue once, polling every task in that batch, then
+    /// wakes any expired timers. Parks on `idle_hook` if that produced no
+    /// work at all and the caller should wait for the next external event.
+    fn poll_pass(&self) {
+        let mut polled_any = false;
+        for task in run_queue::take_all() {
+            polled_any = true;
+            task.poll();
+        }
+
+        let now = time::now();
+        let woke_any = time::wake_expired(now);
+        if polled_any || woke_any {
+            return;
+        }
+
+        let timeout = time::next_deadline().map(|deadline| deadline.saturating_duration_since(now));
+        (self.idle_hook)(timeout);
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
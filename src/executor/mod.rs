@@ -0,0 +1,25 @@
+//! Embedded-style async executor.
+//!
+//! This is the engine's core: an `embassy`-inspired executor that polls
+//! tasks whose futures live in caller-provided `'static` storage rather
+//! than on the heap. See [`TaskStorage`] to create a task, [`Spawner`] to
+//! submit it, and [`Executor`] to drive the poll loop.
+
+mod exec;
+pub mod raw;
+pub(crate) mod run_queue;
+mod spawner;
+
+pub use exec::{Executor, IdleHook};
+pub use raw::TaskStorage;
+pub use spawner::Spawner;
+
+/// Total number of tasks spawned over the program's lifetime.
+pub fn spawned_tasks() -> u32 {
+    raw::spawned_count()
+}
+
+/// Total number of tasks that have run to completion.
+pub fn completed_tasks() -> u32 {
+    raw::completed_count()
+}
@@ -0,0 +1,246 @@
+//! Raw, type-erased task representation.
+//!
+//! A [`TaskHeader`] is the intrusive node stored at the front of every task's
+//! static storage. The executor only ever touches tasks through this header
+//! so that the run queue, the waker and the poll loop can all stay
+//! allocation-free: task futures are pinned in caller-provided `'static`
+//! storage and the header carries just enough state (an atomic status word
+//! and an intrusive `next` pointer) to be queued and polled.
+
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
+use core::task::{Context, RawWaker, RawWakerVTable, Waker};
+
+/// Task has been spawned and is tracked by an executor.
+pub(crate) const STATE_SPAWNED: u32 = 1 << 0;
+/// Task is present in the run queue (either waiting to be polled, or queued
+/// again by a wake that fired while it was being polled).
+pub(crate) const STATE_RUN_QUEUED: u32 = 1 << 1;
+/// Task's future has returned `Poll::Ready` and will not be polled again.
+pub(crate) const STATE_DONE: u32 = 1 << 2;
+
+/// Lifetime counters used for introspection, e.g. by `crate::filter`'s
+/// `task_status` builtin.
+static SPAWNED_COUNT: AtomicU32 = AtomicU32::new(0);
+static COMPLETED_COUNT: AtomicU32 = AtomicU32::new(0);
+
+pub(crate) fn spawned_count() -> u32 {
+    SPAWNED_COUNT.load(Ordering::Relaxed)
+}
+
+pub(crate) fn completed_count() -> u32 {
+    COMPLETED_COUNT.load(Ordering::Relaxed)
+}
+
+/// Type-erased, intrusively-linked task node.
+///
+/// This is the only part of a task the executor core knows the layout of;
+/// the future itself lives in the [`TaskStorage`] that embeds this header
+/// and is reached through `poll_fn`.
+pub struct TaskHeader {
+    pub(crate) state: AtomicU32,
+    pub(crate) next: AtomicPtr<TaskHeader>,
+    pub(crate) poll_fn: unsafe fn(TaskRef),
+}
+
+impl TaskHeader {
+    pub(crate) const fn new(poll_fn: unsafe fn(TaskRef)) -> Self {
+        Self {
+            state: AtomicU32::new(0),
+            next: AtomicPtr::new(core::ptr::null_mut()),
+            poll_fn,
+        }
+    }
+}
+
+/// A non-null pointer to a [`TaskHeader`], erased from its owning
+/// [`TaskStorage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskRef(NonNull<TaskHeader>);
+
+impl TaskRef {
+    fn header(self) -> &'static TaskHeader {
+        // SAFETY: a `TaskRef` is only ever constructed from a `&'static
+        // TaskStorage`, so the header it points at is valid for the
+        // program's lifetime.
+        unsafe { self.0.as_ref() }
+    }
+
+    /// Polls the task's future exactly once through its type-erased
+    /// `poll_fn`.
+    pub(crate) fn poll(self) {
+        let poll_fn = self.header().poll_fn;
+        // SAFETY: `poll_fn` was created alongside this header by
+        // `TaskStorage::new` and always points at the same storage.
+        unsafe { poll_fn(self) }
+    }
+
+    pub(crate) fn as_ptr(self) -> *mut TaskHeader {
+        self.0.as_ptr()
+    }
+
+    /// Sets the RUN_QUEUED bit, returning `true` if the task was not
+    /// already queued (i.e. the caller is responsible for pushing it).
+    pub(crate) fn mark_run_queued(self) -> bool {
+        let prev = self
+            .header()
+            .state
+            .fetch_or(STATE_RUN_QUEUED, Ordering::AcqRel);
+        prev & STATE_RUN_QUEUED == 0
+    }
+
+    /// Clears the RUN_QUEUED bit; called once a popped task has been
+    /// polled so a later wake can queue it again.
+    pub(crate) fn clear_run_queued(self) {
+        self.header()
+            .state
+            .fetch_and(!STATE_RUN_QUEUED, Ordering::AcqRel);
+    }
+
+    /// # Safety
+    /// `ptr` must point at a live `TaskHeader` embedded in a `TaskStorage`
+    /// with `'static` lifetime.
+    pub(crate) unsafe fn from_ptr(ptr: *mut TaskHeader) -> Self {
+        Self(NonNull::new_unchecked(ptr))
+    }
+}
+
+/// Caller-provided, statically allocated storage for a single task.
+///
+/// Placing a future in a `static TaskStorage<F>` and handing it to
+/// [`crate::executor::Spawner::spawn`] is how tasks are created without a
+/// heap: the storage's lifetime is `'static` by construction, so the
+/// executor can keep polling it for as long as the program runs.
+#[repr(C)]
+pub struct TaskStorage<F: Future + 'static> {
+    header: TaskHeader,
+    future: UnsafeCell<MaybeUninit<F>>,
+}
+
+// SAFETY: access to `future` is only ever performed by the executor thread
+// that owns the task (single poller at a time: only the executor, the run
+// queue's sole consumer, polls tasks).
+unsafe impl<F: Future + 'static> Sync for TaskStorage<F> {}
+
+impl<F: Future + 'static> TaskStorage<F> {
+    /// Creates empty, unspawned task storage.
+    pub const fn new() -> Self {
+        Self {
+            header: TaskHeader::new(Self::poll),
+            future: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Initializes the storage with `future` and returns a type-erased
+    /// reference suitable for pushing onto the run queue.
+    ///
+    /// Returns `None` if this storage is already spawned, so a `static`
+    /// cannot accidentally back two concurrent tasks.
+    pub(crate) fn spawn(&'static self, future: F) -> Option<TaskRef> {
+        let prev = self.header.state.fetch_or(STATE_SPAWNED, Ordering::AcqRel);
+        if prev & STATE_SPAWNED != 0 {
+            return None;
+        }
+        // SAFETY: we just claimed exclusive ownership of this storage via
+        // the SPAWNED bit above, and nothing else writes `future` before a
+        // task is spawned.
+        unsafe { (*self.future.get()).write(future) };
+        SPAWNED_COUNT.fetch_add(1, Ordering::Relaxed);
+        log::debug!("task {:p} spawned", &self.header);
+        Some(TaskRef(NonNull::from(&self.header)))
+    }
+
+    /// # Safety
+    /// `task` must be the [`TaskRef`] returned by this storage's `spawn`.
+    unsafe fn poll(task: TaskRef) {
+        // Clear RUN_QUEUED before polling so a waker invoked during this
+        // very poll (e.g. a future waking itself) re-queues the task onto
+        // the fresh queue instead of being swallowed as a no-op.
+        task.clear_run_queued();
+        if task.header().state.load(Ordering::Acquire) & STATE_DONE != 0 {
+            return;
+        }
+        let this = &*(task.as_ptr() as *const TaskHeader as *const Self);
+        let future = Pin::new_unchecked(&mut *(this.future.get() as *mut F));
+        let waker = waker_for(task);
+        let mut cx = Context::from_waker(&waker);
+        log::trace!("task {:p} polled", task.as_ptr());
+        if future.poll(&mut cx).is_ready() {
+            this.header.state.fetch_or(STATE_DONE, Ordering::Release);
+            COMPLETED_COUNT.fetch_add(1, Ordering::Relaxed);
+            log::debug!("task {:p} completed", task.as_ptr());
+        }
+    }
+}
+
+impl<F: Future + 'static> Default for TaskStorage<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the [`Waker`] for `task` from a [`RawWaker`] over its header
+/// pointer, so waking it re-polls only that one task.
+fn waker_for(task: TaskRef) -> Waker {
+    unsafe fn clone(ptr: *const ()) -> RawWaker {
+        RawWaker::new(ptr, &VTABLE)
+    }
+    unsafe fn wake(ptr: *const ()) {
+        wake_by_ref(ptr)
+    }
+    unsafe fn wake_by_ref(ptr: *const ()) {
+        let task = TaskRef::from_ptr(ptr as *mut TaskHeader);
+        log::trace!("task {:p} woken", task.as_ptr());
+        if task.mark_run_queued() {
+            crate::executor::run_queue::push(task);
+        }
+    }
+    unsafe fn drop(_ptr: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+    let raw = RawWaker::new(task.as_ptr() as *const (), &VTABLE);
+    // SAFETY: the vtable's contract (clone/wake/wake_by_ref/drop) is upheld
+    // above and `task` outlives the waker, being `'static`.
+    unsafe { Waker::from_raw(raw) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn noop_poll(_: TaskRef) {}
+
+    #[test]
+    fn mark_run_queued_only_claims_the_push_on_the_transition_into_queued() {
+        static HEADER: TaskHeader = TaskHeader::new(noop_poll);
+        let task = TaskRef(NonNull::from(&HEADER));
+
+        assert!(task.mark_run_queued(), "first wake claims the push");
+        assert!(
+            !task.mark_run_queued(),
+            "a second wake before the task is polled must not re-claim it, \
+             or the waker would push it twice"
+        );
+
+        task.clear_run_queued();
+        assert!(
+            task.mark_run_queued(),
+            "once a popped task has been polled, a later wake may queue it again"
+        );
+    }
+
+    #[test]
+    fn spawn_refuses_to_reinitialize_already_spawned_storage() {
+        static STORAGE: TaskStorage<core::future::Ready<()>> = TaskStorage::new();
+
+        assert!(STORAGE.spawn(core::future::ready(())).is_some());
+        assert!(
+            STORAGE.spawn(core::future::ready(())).is_none(),
+            "a static must not back two concurrent tasks"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Intrusive, lock-free run queue.
+//!
+//! The queue is a singly-linked list threaded through each task's own
+//! [`TaskHeader::next`](super::raw::TaskHeader) pointer, so pushing a task
+//! never allocates. Producers (wakers, possibly running on other threads or
+//! in interrupt context) push with a CAS loop; the executor is the sole
+//! consumer and drains the whole list at once by swapping the head to null,
+//! which is what gives later wakes - fired while the drained batch is being
+//! polled - a fresh list that is only picked up on the next loop iteration.
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::ptr;
+
+use super::raw::TaskHeader;
+use super::raw::TaskRef;
+
+pub(crate) struct RunQueue {
+    head: AtomicPtr<TaskHeader>,
+}
+
+impl RunQueue {
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Pushes `task` onto the front of the queue. Safe to call from any
+    /// number of concurrent wakers.
+    pub(crate) fn push(&self, task: TaskRef) {
+        let node = task.as_ptr();
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: `node` is a valid, live `TaskHeader` for `'static`.
+            unsafe { (*node).next.store(head, Ordering::Relaxed) };
+            match self.head.compare_exchange_weak(
+                head,
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(actual) => head = actual,
+            }
+        }
+    }
+
+    /// Atomically takes the entire queue, leaving it empty for any tasks
+    /// woken while the returned batch is being drained.
+    pub(crate) fn take_all(&self) -> TakeAll {
+        let head = self.head.swap(ptr::null_mut(), Ordering::Acquire);
+        TakeAll { next: head }
+    }
+}
+
+/// Iterator over a batch of tasks popped from a [`RunQueue`].
+pub(crate) struct TakeAll {
+    next: *mut TaskHeader,
+}
+
+impl Iterator for TakeAll {
+    type Item = TaskRef;
+
+    fn next(&mut self) -> Option<TaskRef> {
+        if self.next.is_null() {
+            return None;
+        }
+        // SAFETY: non-null nodes in the list are live `'static` headers.
+        let task = unsafe { TaskRef::from_ptr(self.next) };
+        self.next = unsafe { (*self.next).next.load(Ordering::Relaxed) };
+        Some(task)
+    }
+}
+
+/// Global run queue shared by every executor in the process.
+///
+/// A single process-wide queue (rather than one per [`crate::executor::Executor`])
+/// keeps the waker in [`super::raw`] free of any reference back to the
+/// executor that spawned a task, matching how the `RawWaker` is built purely
+/// from the task header pointer.
+static QUEUE: RunQueue = RunQueue::new();
+
+/// The first thread to have entered [`super::Executor::run`] or
+/// [`super::Executor::run_until`], if any, so that a
+/// push from a waker can unpark it even while it is parked on a timer
+/// deadline rather than spinning on the run queue.
+static EXECUTOR_THREAD: std::sync::OnceLock<std::thread::Thread> = std::sync::OnceLock::new();
+
+pub(crate) fn push(task: TaskRef) {
+    QUEUE.push(task);
+    if let Some(thread) = EXECUTOR_THREAD.get() {
+        thread.unpark();
+    }
+}
+
+pub(crate) fn take_all() -> TakeAll {
+    QUEUE.take_all()
+}
+
+/// Registers the calling thread as the one to unpark on a push. Only the
+/// first call takes effect, matching the one-executor-per-process run queue.
+pub(crate) fn register_executor_thread() {
+    let _ = EXECUTOR_THREAD.set(std::thread::current());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::raw::TaskHeader;
+
+    unsafe fn noop_poll(_: TaskRef) {}
+
+    /// Leaks a `TaskHeader` to get the `'static` lifetime `TaskRef::from_ptr`
+    /// requires, so each test works with its own private queue and headers
+    /// rather than the crate-wide `QUEUE` and its tasks.
+    fn leaked_task() -> TaskRef {
+        let header: &'static TaskHeader = Box::leak(Box::new(TaskHeader::new(noop_poll)));
+        unsafe { TaskRef::from_ptr(header as *const TaskHeader as *mut TaskHeader) }
+    }
+
+    #[test]
+    fn take_all_drains_the_queue_most_recently_pushed_first() {
+        let queue = RunQueue::new();
+        let a = leaked_task();
+        let b = leaked_task();
+        queue.push(a);
+        queue.push(b);
+
+        let drained: Vec<_> = queue.take_all().map(TaskRef::as_ptr).collect();
+        assert_eq!(drained, vec![b.as_ptr(), a.as_ptr()]);
+        assert_eq!(queue.take_all().count(), 0, "take_all must leave the queue empty");
+    }
+
+    #[test]
+    fn push_during_take_all_iteration_lands_on_the_next_drain() {
+        let queue = RunQueue::new();
+        let a = leaked_task();
+        queue.push(a);
+
+        // Snapshot the batch, as the executor does before polling it.
+        let mut batch = queue.take_all();
+        // A waker firing while that batch is still being polled - e.g. a
+        // task waking itself - must not be visible in `batch`.
+        queue.push(a);
+
+        assert_eq!(batch.next().map(TaskRef::as_ptr), Some(a.as_ptr()));
+        assert_eq!(
+            batch.next(),
+            None,
+            "a push after take_all's snapshot must not appear in the batch it already took"
+        );
+
+        let next_batch: Vec<_> = queue.take_all().map(TaskRef::as_ptr).collect();
+        assert_eq!(
+            next_batch,
+            vec![a.as_ptr()],
+            "the self-wake push is only picked up by the next drain"
+        );
+    }
+}
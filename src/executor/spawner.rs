@@ -0,0 +1,39 @@
+//! Handle for submitting tasks to the executor's run queue.
+
+use core::future::Future;
+
+use super::raw::TaskStorage;
+use super::run_queue;
+
+/// Submits tasks backed by caller-provided static storage.
+///
+/// Obtained from [`Executor::spawner`](super::Executor::spawner); cheap to
+/// copy around since it carries no state of its own, only the ability to
+/// reach the global run queue.
+#[derive(Clone, Copy)]
+pub struct Spawner {
+    _private: (),
+}
+
+impl Spawner {
+    pub(crate) const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Initializes `storage` with `future` and queues it to run for the
+    /// first time.
+    ///
+    /// Panics if `storage` has already been spawned, since a `static`
+    /// backing two concurrent tasks would alias its future.
+    pub fn spawn<F: Future<Output = ()> + 'static>(
+        &self,
+        storage: &'static TaskStorage<F>,
+        future: F,
+    ) {
+        let task = storage
+            .spawn(future)
+            .expect("task storage is already spawned");
+        task.mark_run_queued();
+        run_queue::push(task);
+    }
+}
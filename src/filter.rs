@@ -0,0 +1,64 @@
+//! Embeds a `jaq` (jq-in-Rust) interpreter so the `filter` subcommand and
+//! `--filter` option can query and transform the engine's runtime state
+//! with jq-like expressions.
+
+use jaq_interpret::{Args, Ctx, FilterT, Native, ParseCtx, RcIter, Val};
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::{executor, time};
+
+/// Evaluates `expr` with `input` as `.`, returning every value it yields.
+pub fn eval(expr: &str, input: Value) -> Result<Vec<Value>> {
+    let mut ctx = ParseCtx::new(Vec::new());
+    ctx.insert_natives(jaq_core::core());
+    ctx.insert_defs(jaq_std::std());
+    ctx.insert_native("task_status".to_string(), 0, Native::new(task_status));
+    ctx.insert_native("timer_deadline".to_string(), 0, Native::new(timer_deadline));
+    ctx.insert_native("uptime".to_string(), 0, Native::new(uptime));
+
+    let (parsed, errs) = jaq_parse::parse(expr, jaq_parse::main());
+    if !errs.is_empty() {
+        let message = errs.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+        return Err(Error::Filter(message));
+    }
+    // `errs` being empty guarantees `parsed` parsed successfully.
+    let filter = ctx.compile(parsed.expect("jaq_parse reported no errors but returned no filter"));
+    if !ctx.errs.is_empty() {
+        let message = ctx
+            .errs
+            .iter()
+            .map(|(err, _span)| err.to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(Error::Filter(message));
+    }
+
+    let inputs = RcIter::new(core::iter::empty());
+    filter
+        .run((Ctx::new([], &inputs), Val::from(input)))
+        .map(|output| output.map(Value::from).map_err(|err| Error::Filter(format!("{err:?}"))))
+        .collect()
+}
+
+/// Engine-native builtin: the number of tasks spawned and completed so far.
+fn task_status<'a>(_args: Args<'a, Val>, _cv: (Ctx<'a, Val>, Val)) -> jaq_interpret::ValRs<'a> {
+    let status = serde_json::json!({
+        "spawned": executor::spawned_tasks(),
+        "completed": executor::completed_tasks(),
+    });
+    Box::new(core::iter::once(Ok(Val::from(status))))
+}
+
+/// Engine-native builtin: the nearest armed timer deadline, in microseconds
+/// since the clock epoch, or `null` if no timer is armed.
+fn timer_deadline<'a>(_args: Args<'a, Val>, _cv: (Ctx<'a, Val>, Val)) -> jaq_interpret::ValRs<'a> {
+    let deadline = time::next_timer_deadline().map(|d| d.as_micros());
+    Box::new(core::iter::once(Ok(Val::from(serde_json::json!(deadline)))))
+}
+
+/// Engine-native builtin: microseconds elapsed since the clock epoch.
+fn uptime<'a>(_args: Args<'a, Val>, _cv: (Ctx<'a, Val>, Val)) -> jaq_interpret::ValRs<'a> {
+    let micros = time::uptime().as_micros();
+    Box::new(core::iter::once(Ok(Val::from(serde_json::json!(micros)))))
+}
@@ -0,0 +1,43 @@
+//! EmbeddedEngine: an embedded-style async engine built on a static,
+//! no-alloc task executor, driven by the `embeddedengine` CLI.
+
+pub mod executor;
+pub mod time;
+
+mod cli;
+mod error;
+mod filter;
+mod script;
+mod state;
+
+pub use cli::Command;
+pub use error::{Error, Result};
+
+/// Dispatches a parsed CLI [`Command`] to its dedicated handler, then - if
+/// `filter` is set - evaluates it against the resulting engine state.
+///
+/// The `filter` subcommand already is a filter evaluation, so `filter` is
+/// ignored in that case rather than running the expression a second time.
+///
+/// `Run` and `Repl` drive the parsed script as a real task spawned on an
+/// [`executor::Executor`]; the diagnostics that produces (task
+/// spawns/polls/wakes, timer arms/fires, ...) are controlled entirely by
+/// the `log` level the caller configured before invoking `run`, e.g. via
+/// `env_logger` driven by a `clap-verbosity-flag` verbosity flag. `Check`
+/// and `Filter` never touch the executor, so they never log at that level
+/// no matter how it's configured.
+pub fn run(command: Command, filter: Option<&str>) -> Result<()> {
+    match command {
+        Command::Run { file } => cli::run_file(&file)?,
+        Command::Repl => cli::repl()?,
+        Command::Check { file } => cli::check_file(&file)?,
+        Command::Filter { expr } => {
+            cli::run_filter(&expr)?;
+            return Ok(());
+        }
+    }
+    if let Some(expr) = filter {
+        cli::run_filter(expr)?;
+    }
+    Ok(())
+}
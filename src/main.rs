@@ -4,17 +4,27 @@
  */
 
 use clap::Parser;
-use embeddedengine::{Result, run};
+use clap_verbosity_flag::Verbosity;
+use embeddedengine::{run, Command, Result};
 
 #[derive(Parser)]
 #[command(version, about = "EmbeddedEngine - A Rust implementation")]
 struct Cli {
-    /// Enable verbose output
-    #[arg(short, long)]
-    verbose: bool,
+    #[command(flatten)]
+    verbosity: Verbosity,
+
+    /// Evaluate a jq-like expression against the engine state after the command runs
+    #[arg(long, global = true, value_name = "EXPR")]
+    filter: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
 }
 
 fn main() -> Result<()> {
     let args = Cli::parse();
-    run(args.verbose)
+    env_logger::Builder::new()
+        .filter_level(args.verbosity.log_level_filter())
+        .init();
+    run(args.command, args.filter.as_deref())
 }
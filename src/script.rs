@@ -0,0 +1,206 @@
+//! A minimal arithmetic expression language for engine scripts.
+//!
+//! This is deliberately small: numbers, `+ - * /`, parentheses and unary
+//! minus. It exists so the `run`/`check`/`repl` subcommands have a real
+//! parse+evaluate path to share, rather than each inventing its own.
+
+use std::fmt;
+
+/// A parsed script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluates the expression to a single number.
+    pub fn eval(&self) -> f64 {
+        match self {
+            Expr::Number(n) => *n,
+            Expr::Neg(e) => -e.eval(),
+            Expr::Add(a, b) => a.eval() + b.eval(),
+            Expr::Sub(a, b) => a.eval() - b.eval(),
+            Expr::Mul(a, b) => a.eval() * b.eval(),
+            Expr::Div(a, b) => a.eval() / b.eval(),
+        }
+    }
+}
+
+/// A failure to parse a script, with a human-readable `message`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `source` into an [`Expr`].
+pub fn parse(source: &str) -> Result<Expr, ParseError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens: &tokens, pos: 0 };
+    let expr = parser.parse_expr()?;
+    parser.expect_eof()?;
+    Ok(expr)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' => {
+                chars.next();
+                tokens.push(Token::Plus);
+            }
+            '-' => {
+                chars.next();
+                tokens.push(Token::Minus);
+            }
+            '*' => {
+                chars.next();
+                tokens.push(Token::Star);
+            }
+            '/' => {
+                chars.next();
+                tokens.push(Token::Slash);
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() || c == '.' {
+                        text.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let number = text.parse().map_err(|_| ParseError {
+                    message: format!("invalid number literal `{text}`"),
+                })?;
+                tokens.push(Token::Number(number));
+            }
+            other => {
+                return Err(ParseError {
+                    message: format!("unexpected character `{other}`"),
+                })
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn expect_eof(&self) -> Result<(), ParseError> {
+        if self.pos == self.tokens.len() {
+            Ok(())
+        } else {
+            Err(ParseError {
+                message: format!("unexpected trailing token {:?}", self.tokens[self.pos]),
+            })
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.bump();
+                    lhs = Expr::Add(Box::new(lhs), Box::new(self.parse_term()?));
+                }
+                Some(Token::Minus) => {
+                    self.bump();
+                    lhs = Expr::Sub(Box::new(lhs), Box::new(self.parse_term()?));
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    // term := factor (('*' | '/') factor)*
+    fn parse_term(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_factor()?;
+        loop {
+            match self.peek() {
+                Some(Token::Star) => {
+                    self.bump();
+                    lhs = Expr::Mul(Box::new(lhs), Box::new(self.parse_factor()?));
+                }
+                Some(Token::Slash) => {
+                    self.bump();
+                    lhs = Expr::Div(Box::new(lhs), Box::new(self.parse_factor()?));
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    // factor := '-' factor | '(' expr ')' | number
+    fn parse_factor(&mut self) -> Result<Expr, ParseError> {
+        match self.bump() {
+            Some(Token::Minus) => Ok(Expr::Neg(Box::new(self.parse_factor()?))),
+            Some(Token::Number(n)) => Ok(Expr::Number(*n)),
+            Some(Token::LParen) => {
+                let expr = self.parse_expr()?;
+                match self.bump() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(ParseError {
+                        message: "expected closing `)`".to_string(),
+                    }),
+                }
+            }
+            other => Err(ParseError {
+                message: format!("expected an expression, found {other:?}"),
+            }),
+        }
+    }
+}
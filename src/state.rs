@@ -0,0 +1,21 @@
+//! A JSON snapshot of the engine's runtime state, used as the input to
+//! [`crate::filter`] expressions.
+
+use serde_json::{json, Value};
+
+use crate::{executor, time};
+
+/// Captures the engine's current runtime state: task counts, the nearest
+/// armed timer deadline, and uptime.
+pub fn snapshot() -> Value {
+    json!({
+        "uptime_micros": time::uptime().as_micros(),
+        "tasks": {
+            "spawned": executor::spawned_tasks(),
+            "completed": executor::completed_tasks(),
+        },
+        "timer": {
+            "next_deadline_micros": time::next_timer_deadline().map(|d| d.as_micros()),
+        },
+    })
+}
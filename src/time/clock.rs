@@ -0,0 +1,41 @@
+//! Monotonic time source.
+
+use std::sync::OnceLock;
+use std::time::Instant as StdInstant;
+
+use super::Instant;
+
+/// A monotonic clock the timer queue reads deadlines against.
+///
+/// The default [`StdClock`] wraps [`std::time::Instant`]; swap it out with
+/// [`set_clock`] for a hardware tick source on bare-metal targets.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// The default clock, backed by [`std::time::Instant`].
+pub struct StdClock;
+
+impl Clock for StdClock {
+    fn now(&self) -> Instant {
+        static EPOCH: OnceLock<StdInstant> = OnceLock::new();
+        let epoch = EPOCH.get_or_init(StdInstant::now);
+        Instant::from_micros(epoch.elapsed().as_micros() as u64)
+    }
+}
+
+static CLOCK: OnceLock<&'static dyn Clock> = OnceLock::new();
+
+/// Installs `clock` as the source of truth for [`super::now`].
+///
+/// Only the first call takes effect, and none does once the time has been
+/// read: the first read installs [`StdClock`]. The executor reads the time
+/// on every pass, so call this before running it.
+pub fn set_clock(clock: &'static dyn Clock) {
+    let _ = CLOCK.set(clock);
+}
+
+pub(crate) fn now() -> Instant {
+    CLOCK.get_or_init(|| &StdClock as &'static dyn Clock).now()
+}
@@ -0,0 +1,87 @@
+//! `embassy`-style time: a monotonic [`Instant`]/[`Duration`] pair and a
+//! [`Timer`] future so tasks can sleep without busy-looping the executor.
+
+mod clock;
+mod queue;
+mod timer;
+
+pub use clock::{set_clock, Clock, StdClock};
+pub use timer::Timer;
+
+pub(crate) use clock::now;
+pub(crate) use queue::{next_deadline, wake_expired};
+
+/// Elapsed time since the active [`Clock`]'s epoch.
+pub fn uptime() -> Duration {
+    Duration::from_micros(now().as_micros())
+}
+
+/// The nearest deadline among currently-armed [`Timer`]s, if any.
+pub fn next_timer_deadline() -> Option<Instant> {
+    queue::next_deadline()
+}
+
+/// A point in time, in microseconds since an unspecified epoch fixed by the
+/// active [`Clock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instant(u64);
+
+impl Instant {
+    pub(crate) const fn from_micros(micros: u64) -> Self {
+        Self(micros)
+    }
+
+    /// Microseconds since the clock's epoch.
+    pub const fn as_micros(self) -> u64 {
+        self.0
+    }
+
+    /// `self - earlier`, or `None` if `earlier` is after `self`.
+    pub fn checked_duration_since(self, earlier: Instant) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration)
+    }
+
+    /// `self - earlier`, clamped to zero instead of underflowing.
+    pub fn saturating_duration_since(self, earlier: Instant) -> Duration {
+        Duration(self.0.saturating_sub(earlier.0))
+    }
+}
+
+impl core::ops::Add<Duration> for Instant {
+    type Output = Instant;
+    fn add(self, rhs: Duration) -> Instant {
+        Instant(self.0 + rhs.0)
+    }
+}
+
+/// A span of time, in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(u64);
+
+impl Duration {
+    /// Creates a `Duration` from a number of microseconds.
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(micros)
+    }
+
+    /// Creates a `Duration` from a number of milliseconds.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis * 1_000)
+    }
+
+    /// Creates a `Duration` from a number of seconds.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs * 1_000_000)
+    }
+
+    /// This duration as a number of microseconds.
+    pub const fn as_micros(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<Duration> for std::time::Duration {
+    fn from(d: Duration) -> Self {
+        std::time::Duration::from_micros(d.0)
+    }
+}
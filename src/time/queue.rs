@@ -0,0 +1,145 @@
+//! Process-wide timer queue.
+//!
+//! There is one queue, shared by every executor in the process (like the
+//! run queue), not one queue per executor.
+//!
+//! Pending [`Timer`](super::Timer) deadlines are kept in a binary min-heap
+//! keyed on [`Instant`], so the executor can always ask for the single
+//! nearest deadline in `O(1)` and pop expired entries in sorted order.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Mutex;
+use std::task::Waker;
+
+use super::Instant;
+
+struct TimerEntry {
+    deadline: Instant,
+    waker: Waker,
+}
+
+impl PartialEq for TimerEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+impl Eq for TimerEntry {}
+impl PartialOrd for TimerEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for TimerEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.deadline.cmp(&other.deadline)
+    }
+}
+
+struct TimerQueue {
+    heap: Mutex<BinaryHeap<Reverse<TimerEntry>>>,
+}
+
+impl TimerQueue {
+    const fn new() -> Self {
+        Self {
+            heap: Mutex::new(BinaryHeap::new()),
+        }
+    }
+
+    fn schedule(&self, deadline: Instant, waker: Waker) {
+        log::debug!("timer armed for {:?}", deadline);
+        self.heap
+            .lock()
+            .unwrap()
+            .push(Reverse(TimerEntry { deadline, waker }));
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.heap.lock().unwrap().peek().map(|e| e.0.deadline)
+    }
+
+    /// Wakes (and removes) every timer whose deadline is at or before `now`.
+    /// Returns whether any timer fired.
+    fn wake_expired(&self, now: Instant) -> bool {
+        let mut heap = self.heap.lock().unwrap();
+        let mut woke_any = false;
+        while matches!(heap.peek(), Some(Reverse(entry)) if entry.deadline <= now) {
+            let Reverse(entry) = heap.pop().unwrap();
+            log::debug!("timer fired for {:?}", entry.deadline);
+            entry.waker.wake();
+            woke_any = true;
+        }
+        woke_any
+    }
+}
+
+static TIMER_QUEUE: TimerQueue = TimerQueue::new();
+
+pub(crate) fn schedule(deadline: Instant, waker: Waker) {
+    TIMER_QUEUE.schedule(deadline, waker);
+}
+
+pub(crate) fn next_deadline() -> Option<Instant> {
+    TIMER_QUEUE.next_deadline()
+}
+
+pub(crate) fn wake_expired(now: Instant) -> bool {
+    TIMER_QUEUE.wake_expired(now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    /// A waker that records its id in a shared log instead of waking a real
+    /// task, so tests can assert both *that* and *in what order* timers fired.
+    struct Recorder {
+        id: u32,
+        log: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl Wake for Recorder {
+        fn wake(self: Arc<Self>) {
+            self.log.lock().unwrap().push(self.id);
+        }
+    }
+
+    fn recorder(id: u32, log: &Arc<Mutex<Vec<u32>>>) -> Waker {
+        Waker::from(Arc::new(Recorder { id, log: log.clone() }))
+    }
+
+    #[test]
+    fn wake_expired_fires_only_due_timers_in_deadline_order() {
+        let queue = TimerQueue::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        queue.schedule(Instant::from_micros(30), recorder(30, &log));
+        queue.schedule(Instant::from_micros(10), recorder(10, &log));
+        queue.schedule(Instant::from_micros(20), recorder(20, &log));
+
+        assert!(queue.wake_expired(Instant::from_micros(25)));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![10, 20],
+            "only deadlines at or before `now` fire, earliest first"
+        );
+        assert_eq!(queue.next_deadline(), Some(Instant::from_micros(30)));
+
+        assert!(queue.wake_expired(Instant::from_micros(30)));
+        assert_eq!(*log.lock().unwrap(), vec![10, 20, 30]);
+        assert_eq!(queue.next_deadline(), None);
+    }
+
+    #[test]
+    fn wake_expired_is_a_no_op_when_nothing_is_due() {
+        let queue = TimerQueue::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        queue.schedule(Instant::from_micros(100), recorder(100, &log));
+
+        assert!(!queue.wake_expired(Instant::from_micros(50)));
+        assert!(log.lock().unwrap().is_empty());
+        assert_eq!(queue.next_deadline(), Some(Instant::from_micros(100)));
+    }
+}
@@ -0,0 +1,49 @@
+//! A future that completes after a deadline, backed by the timer queue.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use super::{now, queue, Duration, Instant};
+
+/// A future that resolves once the active [`Clock`](super::Clock) reads at
+/// or past `deadline`.
+///
+/// Polling a pending `Timer` registers its waker with the timer queue
+/// exactly once, so the executor can park until the nearest deadline
+/// instead of busy-polling.
+pub struct Timer {
+    deadline: Instant,
+    registered: bool,
+}
+
+impl Timer {
+    /// A timer that fires `duration` from now.
+    pub fn after(duration: Duration) -> Self {
+        Self::at(now() + duration)
+    }
+
+    /// A timer that fires at the given absolute `deadline`.
+    pub fn at(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            registered: false,
+        }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        if now() >= this.deadline {
+            return Poll::Ready(());
+        }
+        if !this.registered {
+            queue::schedule(this.deadline, cx.waker().clone());
+            this.registered = true;
+        }
+        Poll::Pending
+    }
+}